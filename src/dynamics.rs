@@ -0,0 +1,169 @@
+use liealg::{Algebra, Group, SE3};
+use nalgebra::{Matrix3, Matrix6, Vector3, Vector6};
+use petgraph::Direction::{Incoming, Outgoing};
+
+use super::Model;
+
+// Skew-symmetric matrix of a 3-vector, i.e. the `hat` of `v` such that
+// `skew(v) * w == v.cross(&w)`.
+fn skew(v: Vector3<f64>) -> Matrix3<f64> {
+    Matrix3::new(0., -v.z, v.y, v.z, 0., -v.x, -v.y, v.x, 0.)
+}
+
+// 6x6 matrix representation of the small adjoint `ad_v`, the linear map for
+// the Lie bracket `[v, ·]` of two se(3) twists (stacked as `[ω; v]`).
+fn ad_matrix(twist: Vector6<f64>) -> Matrix6<f64> {
+    let omega = skew(twist.fixed_rows::<3>(0).into_owned());
+    let v = skew(twist.fixed_rows::<3>(3).into_owned());
+    let mut m = Matrix6::zeros();
+    m.fixed_view_mut::<3, 3>(0, 0).copy_from(&omega);
+    m.fixed_view_mut::<3, 3>(3, 0).copy_from(&v);
+    m.fixed_view_mut::<3, 3>(3, 3).copy_from(&omega);
+    m
+}
+
+// 6x6 matrix representation of the group adjoint `Ad_t`.
+fn adjoint_matrix(t: &SE3<f64>) -> Matrix6<f64> {
+    Matrix6::from_column_slice(t.adjoint().as_slice())
+}
+
+impl Model {
+    /// Recursive Newton–Euler inverse dynamics: joint torques for the given
+    /// configuration, velocity, acceleration and gravity vector.
+    ///
+    /// Forward pass, root to leaves in `bfs` order, propagates each link's
+    /// twist `V_i = Ad_{T_{i,parent}} V_parent + A_i θ̇_i` and acceleration
+    /// `V̇_i = Ad_{T_{i,parent}} V̇_parent + ad_{V_i}(A_i) θ̇_i + A_i θ̈_i`,
+    /// with `T_{i,parent} = exp([-A_i]θ_i) * parent_zero_pose^{-1}` and `A_i`
+    /// the link's `local_spatial_screw`. Gravity is folded in by seeding the
+    /// base acceleration with `-gravity`. Backward pass, leaves to root,
+    /// propagates the wrench
+    /// `F_i = Ad^T_{T_{child,i}} F_child + G_i V̇_i - ad^T_{V_i}(G_i V_i)`,
+    /// where `G_i` is `local_spatial_inertial`, and reads off the joint
+    /// torque `τ_i = F_i^T A_i`.
+    ///
+    /// Mimic joints resolve `theta`/`dtheta`/`ddtheta` from their source
+    /// joint the same way `forward_kinematics` does, via
+    /// [`Model::resolved_position`]/[`Model::resolved_rate`]; the constant
+    /// `offset` only applies to position, since it drops out of any time
+    /// derivative.
+    pub fn inverse_dynamics(
+        &self,
+        theta: &[f64],
+        dtheta: &[f64],
+        ddtheta: &[f64],
+        gravity: [f64; 3],
+    ) -> Vec<f64> {
+        let n = self.links.len();
+        let mut configs = vec![SE3::identity(); n];
+        let mut twists = vec![Vector6::zeros(); n];
+        let mut accels = vec![Vector6::zeros(); n];
+
+        let mut base_accel = Vector6::zeros();
+        base_accel
+            .fixed_rows_mut::<3>(3)
+            .copy_from(&(-Vector3::from(gravity)));
+
+        for &i in &self.bfs {
+            let link = &self.links[i];
+            let a_vec = link.local_spatial_screw.vee();
+            let theta_i = self.resolved_position(i, theta);
+            let dtheta_i = self.resolved_rate(i, dtheta);
+            let ddtheta_i = self.resolved_rate(i, ddtheta);
+
+            let t_i_parent = link.local_spatial_screw.exp(-theta_i) * link.parent_zero_pose.inv();
+            let ad_t = adjoint_matrix(&t_i_parent);
+
+            let parent = self.link_graph.neighbors_directed(i, Incoming).next();
+            let (parent_twist, parent_accel) = match parent {
+                Some(p) => (twists[p], accels[p]),
+                None => (Vector6::zeros(), base_accel),
+            };
+
+            let v_i = ad_t * parent_twist + a_vec * dtheta_i;
+            let vdot_i = ad_t * parent_accel + ad_matrix(v_i) * a_vec * dtheta_i + a_vec * ddtheta_i;
+
+            configs[i] = t_i_parent;
+            twists[i] = v_i;
+            accels[i] = vdot_i;
+        }
+
+        let mut wrenches = vec![Vector6::zeros(); n];
+        let mut torques = vec![0.; n];
+
+        for &i in self.bfs.iter().rev() {
+            let link = &self.links[i];
+            let g_i = link.local_spatial_inertial;
+            let v_i = twists[i];
+            let vdot_i = accels[i];
+
+            let mut f_i = self
+                .link_graph
+                .neighbors_directed(i, Outgoing)
+                .fold(Vector6::zeros(), |acc, child| {
+                    acc + adjoint_matrix(&configs[child]).transpose() * wrenches[child]
+                });
+            f_i += g_i * vdot_i - ad_matrix(v_i).transpose() * (g_i * v_i);
+
+            torques[i] = f_i.dot(&link.local_spatial_screw.vee());
+            wrenches[i] = f_i;
+        }
+
+        torques
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use liealg::se3;
+    use petgraph::graphmap::DiGraphMap;
+
+    use super::*;
+    use crate::{Joint, Link};
+
+    // a single prismatic link, free-floating at the origin, whose point
+    // mass sits at the joint itself (no lever arm), so closed-form gravity
+    // loading is just `F = mass * g` along the joint's translation axis
+    fn single_prismatic_link_model(mass: f64) -> Model {
+        let mut inertia = Matrix6::zeros();
+        inertia
+            .fixed_view_mut::<3, 3>(3, 3)
+            .copy_from(&(Matrix3::identity() * mass));
+
+        let joint_screw = se3::<f64>::new([0., 0., 0.], [0., 0., 1.]);
+        Model {
+            links: vec![Link {
+                space_spatial_screw: joint_screw.clone(),
+                local_spatial_screw: joint_screw,
+                global_zero_pose: SE3::identity(),
+                parent_zero_pose: SE3::identity(),
+                local_spatial_inertial: inertia,
+                joint: Joint {
+                    urdf_joint: None,
+                    mimic: None,
+                    limits: None,
+                },
+            }],
+            link_graph: DiGraphMap::new(),
+            bfs: vec![0],
+        }
+    }
+
+    #[test]
+    fn inverse_dynamics_zero_motion_zero_gravity_has_no_torque() {
+        let model = single_prismatic_link_model(1.0);
+        let torques = model.inverse_dynamics(&[0.], &[0.], &[0.], [0., 0., 0.]);
+        assert!(torques[0].abs() < 1e-12);
+    }
+
+    #[test]
+    fn inverse_dynamics_matches_closed_form_static_gravity_load() {
+        let mass = 2.0;
+        let g = 9.8;
+        let model = single_prismatic_link_model(mass);
+        // no velocity/acceleration: the joint just has to hold the mass
+        // against gravity, a force of `mass * g` along its translation axis
+        let torques = model.inverse_dynamics(&[0.], &[0.], &[0.], [0., 0., -g]);
+        assert!((torques[0] - mass * g).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use liealg::{Adjoint, Algebra, Group, SE3};
+use nalgebra::DMatrix;
+
+use super::Model;
+
+impl Model {
+    /// Global pose of every link at joint configuration `theta`.
+    ///
+    /// Evaluates the space-form product of exponentials
+    /// `T_i = (∏_{j ∈ ancestors(i)} exp([S_j] θ_j)) * M_i`, where `S_j` is
+    /// that link's `space_spatial_screw` and `M_i` its `global_zero_pose`.
+    /// Links are walked in `bfs` order so each child reuses its parent's
+    /// accumulated exponential product. Fixed joints carry an identity
+    /// screw, so they contribute no rotation/translation regardless of
+    /// `theta`. Mimic joints resolve their value from their source joint
+    /// instead of reading `theta` directly. The returned poses are indexed
+    /// to match `links`.
+    pub fn forward_kinematics(&self, theta: &[f64]) -> Vec<SE3<f64>> {
+        let products = self.exponential_products(theta);
+        self.links
+            .iter()
+            .enumerate()
+            .map(|(i, link)| {
+                products
+                    .get(&i)
+                    .cloned()
+                    .unwrap_or_else(SE3::identity)
+                    * link.global_zero_pose.clone()
+            })
+            .collect()
+    }
+
+    // Running product of exponentials up to and including each link's own
+    // joint, i.e. `exp([S_1]θ_1)...exp([S_i]θ_i)`. Shared by
+    // `forward_kinematics` and the Jacobian computations below.
+    fn exponential_products(&self, theta: &[f64]) -> HashMap<usize, SE3<f64>> {
+        let mut products = HashMap::with_capacity(self.links.len());
+        for &i in &self.bfs {
+            let parent = self
+                .link_graph
+                .neighbors_directed(i, petgraph::Direction::Incoming)
+                .next();
+            let parent_product = parent
+                .and_then(|p| products.get(&p))
+                .cloned()
+                .unwrap_or_else(SE3::identity);
+            let exp_i = self.links[i]
+                .space_spatial_screw
+                .exp(self.resolved_position(i, theta));
+            products.insert(i, parent_product * exp_i);
+        }
+        products
+    }
+
+    /// Space Jacobian of `target_link` at configuration `theta`.
+    ///
+    /// Column `i`, for each link `i` of [`Model::free_chain`] (the
+    /// root-to-`target_link` chain without mimic joints; the root link and
+    /// fixed-joint links have an identity screw and so a zero column), is
+    /// `J_{s,i} = Ad_{exp([S_1]θ_1)···exp([S_{i-1}]θ_{i-1})}(S_i)`: the
+    /// running exponential product up to (but not including) link `i`'s own
+    /// joint, applied to its `space_spatial_screw` via the group adjoint.
+    /// Mimic joints still move the chain (their value is resolved from their
+    /// source) but carry no independent column, since they have no free
+    /// entry in `theta`; see [`Model::free_chain`].
+    pub fn space_jacobian_of(&self, theta: &[f64], target_link: usize) -> DMatrix<f64> {
+        let products = self.exponential_products(theta);
+        let free_chain = self.free_chain(target_link);
+
+        let mut jacobian = DMatrix::<f64>::zeros(6, free_chain.len());
+        for (col, &i) in free_chain.iter().enumerate() {
+            let parent = self
+                .link_graph
+                .neighbors_directed(i, petgraph::Direction::Incoming)
+                .next();
+            let pre = parent
+                .and_then(|p| products.get(&p))
+                .cloned()
+                .unwrap_or_else(SE3::identity);
+            let column = pre.adjoint().act(&self.links[i].space_spatial_screw).vee();
+            jacobian.column_mut(col).copy_from(&column);
+        }
+        jacobian
+    }
+
+    /// Space Jacobian at the default end-effector link, see
+    /// [`Model::end_effector`].
+    pub fn space_jacobian(&self, theta: &[f64]) -> DMatrix<f64> {
+        self.space_jacobian_of(theta, self.end_effector())
+    }
+
+    /// Body Jacobian of `target_link`: `J_b = Ad_{T^{-1}} J_s`, with `T` the
+    /// current pose of `target_link`.
+    pub fn body_jacobian_of(&self, theta: &[f64], target_link: usize) -> DMatrix<f64> {
+        let target_pose = self.forward_kinematics(theta)[target_link].clone();
+        let space = self.space_jacobian_of(theta, target_link);
+        let adjoint = target_pose.inv().adjoint();
+        let adjoint = DMatrix::from_column_slice(6, 6, adjoint.as_slice());
+        adjoint * space
+    }
+
+    /// Body Jacobian at the default end-effector link, see
+    /// [`Model::end_effector`].
+    pub fn body_jacobian(&self, theta: &[f64]) -> DMatrix<f64> {
+        self.body_jacobian_of(theta, self.end_effector())
+    }
+
+    // Root-to-`link` chain of link ids, root first.
+    fn ancestor_chain(&self, link: usize) -> Vec<usize> {
+        let mut chain = vec![link];
+        let mut current = link;
+        while let Some(parent) = self
+            .link_graph
+            .neighbors_directed(current, petgraph::Direction::Incoming)
+            .next()
+        {
+            chain.push(parent);
+            current = parent;
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// The root-to-`link` chain, excluding mimic joints: these still move
+    /// (driven by their source joint) but have no free entry in `theta`, so
+    /// they carry no Jacobian column and no independent IK update.
+    pub fn free_chain(&self, link: usize) -> Vec<usize> {
+        self.ancestor_chain(link)
+            .into_iter()
+            .filter(|&i| self.links[i].joint.mimic.is_none())
+            .collect()
+    }
+
+    /// A leaf link with no children, used as the default Jacobian/IK target
+    /// when the caller does not name one explicitly.
+    pub fn end_effector(&self) -> usize {
+        self.bfs
+            .iter()
+            .rev()
+            .find(|&&i| {
+                self.link_graph
+                    .neighbors_directed(i, petgraph::Direction::Outgoing)
+                    .next()
+                    .is_none()
+            })
+            .copied()
+            .unwrap_or_else(|| self.links.len() - 1)
+    }
+
+    /// Newton–Raphson inverse kinematics on the body twist, the approach
+    /// used by the `k` kinematics crate.
+    ///
+    /// Starting from `theta0`, repeatedly forms the body error twist
+    /// `V_b = log(T_sb^{-1} * desired)` between the current pose of
+    /// `target_link` and `desired`, and updates
+    /// `theta += pinv(J_b) * V_b`, clamping to each joint's `JointLimits`
+    /// after every update so the solver never leaves the feasible set.
+    /// Returns `Some(theta)` once the angular and linear error both fall
+    /// under `EPS_OMEGA`/`EPS_V`, or `None` if it fails to converge within
+    /// `MAX_ITERS` iterations.
+    pub fn inverse_kinematics(
+        &self,
+        target_link: usize,
+        desired: &SE3<f64>,
+        theta0: &[f64],
+    ) -> Option<Vec<f64>> {
+        const MAX_ITERS: usize = 20;
+        const EPS_OMEGA: f64 = 1e-3;
+        const EPS_V: f64 = 1e-4;
+
+        let mut theta = theta0.to_vec();
+        let free_chain = self.free_chain(target_link);
+
+        for _ in 0..MAX_ITERS {
+            let t_sb = self.forward_kinematics(&theta)[target_link].clone();
+            let error = t_sb.inv() * desired.clone();
+            let v_b = error.log().vee();
+
+            let omega_norm = v_b.fixed_rows::<3>(0).norm();
+            let v_norm = v_b.fixed_rows::<3>(3).norm();
+            if omega_norm < EPS_OMEGA && v_norm < EPS_V {
+                return Some(theta);
+            }
+
+            let jacobian = self.body_jacobian_of(&theta, target_link);
+            let pinv = jacobian.svd(true, true).pseudo_inverse(1e-9).ok()?;
+            let delta = pinv * v_b;
+
+            for (col, &link) in free_chain.iter().enumerate() {
+                theta[link] += delta[col];
+            }
+            self.clamp_configuration(&mut theta);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forward_kinematics_zero_matches_global_zero_pose() {
+        let model = Model::from_urdf("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf").unwrap();
+        let zeros = vec![0.; model.links.len()];
+        let poses = model.forward_kinematics(&zeros);
+        for (pose, link) in poses.iter().zip(&model.links) {
+            let error = (pose.clone().inv() * link.global_zero_pose.clone()).log().vee();
+            assert!(error.norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn space_jacobian_has_one_column_per_free_joint() {
+        let model = Model::from_urdf("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf").unwrap();
+        let end_effector = model.end_effector();
+        let theta = vec![0.; model.links.len()];
+        let jacobian = model.space_jacobian_of(&theta, end_effector);
+        assert_eq!(jacobian.ncols(), model.free_chain(end_effector).len());
+    }
+
+    #[test]
+    fn inverse_kinematics_recovers_known_configuration() {
+        let model = Model::from_urdf("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf").unwrap();
+        let end_effector = model.end_effector();
+        let target_theta = vec![0.1; model.links.len()];
+        let desired = model.forward_kinematics(&target_theta)[end_effector].clone();
+
+        let theta0 = vec![0.; model.links.len()];
+        let solution = model
+            .inverse_kinematics(end_effector, &desired, &theta0)
+            .expect("IK should converge from a nearby seed");
+
+        let achieved = model.forward_kinematics(&solution)[end_effector].clone();
+        let error = (achieved.inv() * desired).log().vee();
+        assert!(error.norm() < 1e-3);
+    }
+}
@@ -3,15 +3,46 @@ use nalgebra::Matrix6;
 use petgraph::prelude::*;
 
 mod bfs;
+mod dynamics;
+mod kinematics;
+mod limits;
+mod mimic;
+mod source;
 mod spatial_inertial;
 mod urdf;
 mod utils;
 
+// a mimic joint's value is derived from another joint rather than driven
+// independently: theta = multiplier * theta_source + offset
+#[derive(Debug, Clone)]
+pub struct Mimic {
+    pub source: usize,
+    pub multiplier: f64,
+    pub offset: f64,
+}
+
+// URDF <limit> data; continuous joints have no position limit and so carry
+// no `JointLimits` at all (see `Joint::limits`).
+#[derive(Debug, Clone, Copy)]
+pub struct JointLimits {
+    pub lower: f64,
+    pub upper: f64,
+    pub velocity: f64,
+    pub effort: f64,
+}
+
 #[derive(Debug)]
 pub struct Joint {
     pub urdf_joint: Option<urdf_rs::Joint>,
+    pub mimic: Option<Mimic>,
+    pub limits: Option<JointLimits>,
 }
 
+// only fixed and single-DOF joints (revolute, continuous, prismatic) are
+// represented: `Link::joint`'s screw is the one axis forward/inverse
+// kinematics and dynamics drive. Multi-DOF joints (`Planar`, `Floating`)
+// have no single axis to assign here, so parsing a URDF that uses one fails
+// instead of silently picking one of its axes (see `fullfill_link_map`).
 #[derive(Debug)]
 pub struct Link {
     pub space_spatial_screw: se3<f64>,
@@ -0,0 +1,165 @@
+use rand::Rng;
+
+use super::Model;
+
+// continuous joints have no URDF limit; sample them from a generous default
+// range instead of an unbounded one
+const CONTINUOUS_RANGE: f64 = std::f64::consts::PI;
+
+impl Model {
+    /// Saturate each movable joint in `theta` to its `[lower, upper]`
+    /// `JointLimits`. Joints without limits (fixed, continuous, or any joint
+    /// type not yet covered by `JointLimits`) are left untouched.
+    ///
+    /// A mimic joint has no independent entry of its own (its value is
+    /// `multiplier * theta[source] + offset`), so its limits are enforced by
+    /// inverting that relationship and clamping `theta[source]` instead. If
+    /// several mimic joints share a source with conflicting limits, each is
+    /// applied in turn and the last one wins.
+    pub fn clamp_configuration(&self, theta: &mut [f64]) {
+        for (i, link) in self.links.iter().enumerate() {
+            if link.joint.mimic.is_some() {
+                continue;
+            }
+            if let (Some(limits), Some(value)) = (&link.joint.limits, theta.get_mut(i)) {
+                *value = value.clamp(limits.lower, limits.upper);
+            }
+        }
+
+        for link in &self.links {
+            let (Some(mimic), Some(limits)) = (&link.joint.mimic, &link.joint.limits) else {
+                continue;
+            };
+            if mimic.multiplier == 0. {
+                continue;
+            }
+            let Some(source) = theta.get_mut(mimic.source) else {
+                continue;
+            };
+            let (lower, upper) = (
+                (limits.lower - mimic.offset) / mimic.multiplier,
+                (limits.upper - mimic.offset) / mimic.multiplier,
+            );
+            // dividing by a negative multiplier flips which bound is which
+            let (lower, upper) = if mimic.multiplier > 0. {
+                (lower, upper)
+            } else {
+                (upper, lower)
+            };
+            *source = source.clamp(lower, upper);
+        }
+    }
+
+    /// Whether every movable joint in `theta` lies within its `JointLimits`,
+    /// checking each mimic joint's derived value (see
+    /// [`Model::resolved_position`]) rather than its unused entry in `theta`.
+    /// Joints without limits always pass.
+    pub fn is_within_limits(&self, theta: &[f64]) -> bool {
+        self.links.iter().enumerate().all(|(i, link)| {
+            let Some(limits) = &link.joint.limits else {
+                return true;
+            };
+            let value = self.resolved_position(i, theta);
+            value >= limits.lower && value <= limits.upper
+        })
+    }
+
+    /// A configuration sampled uniformly within each joint's `JointLimits`.
+    /// Joints without limits (e.g. continuous) are sampled from
+    /// `[-CONTINUOUS_RANGE, CONTINUOUS_RANGE]`.
+    pub fn random_configuration(&self, rng: &mut impl Rng) -> Vec<f64> {
+        self.links
+            .iter()
+            .map(|link| match &link.joint.limits {
+                Some(limits) => rng.gen_range(limits.lower..=limits.upper),
+                None => rng.gen_range(-CONTINUOUS_RANGE..=CONTINUOUS_RANGE),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use liealg::{se3, Group, SE3};
+    use nalgebra::Matrix6;
+    use petgraph::graphmap::DiGraphMap;
+
+    use super::*;
+    use crate::{Joint, JointLimits, Link, Mimic};
+
+    fn link(limits: Option<JointLimits>, mimic: Option<Mimic>) -> Link {
+        Link {
+            space_spatial_screw: se3::identity(),
+            local_spatial_screw: se3::identity(),
+            global_zero_pose: SE3::identity(),
+            parent_zero_pose: SE3::identity(),
+            local_spatial_inertial: Matrix6::zeros(),
+            joint: Joint {
+                urdf_joint: None,
+                mimic,
+                limits,
+            },
+        }
+    }
+
+    // a driver joint and a mimic joint modelling a parallel gripper: the
+    // mimic finger's limit is tighter than the driver's, so it can only be
+    // enforced by inverting the mimic relationship back onto the driver
+    fn gripper_model() -> Model {
+        let driver_limits = JointLimits {
+            lower: -1.0,
+            upper: 1.0,
+            velocity: 1.0,
+            effort: 1.0,
+        };
+        let mimic_limits = JointLimits {
+            lower: -0.5,
+            upper: 0.5,
+            velocity: 1.0,
+            effort: 1.0,
+        };
+        Model {
+            links: vec![
+                link(Some(driver_limits), None),
+                link(
+                    Some(mimic_limits),
+                    Some(Mimic {
+                        source: 0,
+                        multiplier: 1.0,
+                        offset: 0.,
+                    }),
+                ),
+            ],
+            link_graph: DiGraphMap::new(),
+            bfs: vec![0, 1],
+        }
+    }
+
+    #[test]
+    fn clamp_configuration_bounds_mimic_joint() {
+        let model = gripper_model();
+        // within the driver's own [-1, 1] limit, but drives the mimic
+        // finger's derived value past its tighter [-0.5, 0.5]
+        let mut theta = vec![0.9, 0.];
+        model.clamp_configuration(&mut theta);
+        assert!(theta[0] <= 0.5 + 1e-9);
+        assert!(model.is_within_limits(&theta));
+    }
+
+    #[test]
+    fn is_within_limits_checks_resolved_mimic_value() {
+        let model = gripper_model();
+        assert!(!model.is_within_limits(&[0.9, 0.]));
+        assert!(model.is_within_limits(&[0.3, 0.]));
+    }
+
+    #[test]
+    fn random_configuration_respects_fixture_limits() {
+        let model = Model::from_urdf("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf").unwrap();
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let theta = model.random_configuration(&mut rng);
+            assert!(model.is_within_limits(&theta));
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use super::Model;
+
+impl Model {
+    /// A joint's position: for a mimic joint, `multiplier * source + offset`
+    /// read from its source joint's own slot in `values`; for an
+    /// independently driven joint, `values[link]` itself.
+    pub(crate) fn resolved_position(&self, link: usize, values: &[f64]) -> f64 {
+        match &self.links[link].joint.mimic {
+            Some(mimic) => {
+                mimic.multiplier * values.get(mimic.source).copied().unwrap_or(0.) + mimic.offset
+            }
+            None => values.get(link).copied().unwrap_or(0.),
+        }
+    }
+
+    /// A joint's velocity or acceleration: for a mimic joint, `multiplier *
+    /// source` (the constant `offset` drops out of any time derivative); for
+    /// an independently driven joint, `values[link]` itself.
+    pub(crate) fn resolved_rate(&self, link: usize, values: &[f64]) -> f64 {
+        match &self.links[link].joint.mimic {
+            Some(mimic) => mimic.multiplier * values.get(mimic.source).copied().unwrap_or(0.),
+            None => values.get(link).copied().unwrap_or(0.),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Where a URDF document comes from. Implementations own just enough to fetch
+// their bytes; `Model::from_urdf_with_packages` picks one from a URL and
+// parses what it loads into a `Model`.
+pub trait UrdfSource {
+    fn load(&self) -> std::io::Result<String>;
+}
+
+/// A URDF file on the local filesystem.
+pub struct FileSource {
+    pub path: PathBuf,
+}
+
+impl UrdfSource for FileSource {
+    fn load(&self) -> std::io::Result<String> {
+        let mut file = std::fs::File::open(&self.path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        Ok(contents)
+    }
+}
+
+/// A URDF document served over HTTP(S). Requires the `web` feature.
+#[cfg(feature = "web")]
+pub struct HttpSource {
+    pub url: String,
+}
+
+#[cfg(feature = "web")]
+impl UrdfSource for HttpSource {
+    fn load(&self) -> std::io::Result<String> {
+        let mut response = reqwest::blocking::get(&self.url).map_err(std::io::Error::other)?;
+        let mut contents = String::new();
+        response.read_to_string(&mut contents)?;
+        Ok(contents)
+    }
+}
+
+/// A ROS-style `package://<package>/<path>` reference, resolved against a
+/// caller-supplied map of package name to its directory on disk.
+pub struct PackageSource<'a> {
+    pub package: String,
+    pub relative_path: String,
+    pub package_paths: &'a HashMap<String, PathBuf>,
+}
+
+impl UrdfSource for PackageSource<'_> {
+    fn load(&self) -> std::io::Result<String> {
+        let base = self.package_paths.get(&self.package).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("unknown ROS package `{}`, pass its path in `package_paths`", self.package),
+            )
+        })?;
+        FileSource {
+            path: base.join(&self.relative_path),
+        }
+        .load()
+    }
+}
+
+/// Splits a `package://<package>/<path>` URI into its package name and
+/// relative path, or `None` if `url` is not a `package://` URI.
+pub(super) fn parse_package_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("package://")?;
+    rest.split_once('/')
+}
+
+pub(super) fn is_weburl(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+pub(super) fn file_source(url: &str) -> FileSource {
+    FileSource {
+        path: Path::new(url).to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = "./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf";
+
+    #[test]
+    fn file_source_reads_fixture() {
+        let contents = FileSource {
+            path: PathBuf::from(FIXTURE),
+        }
+        .load()
+        .unwrap();
+        assert!(contents.contains("<robot"));
+    }
+
+    #[test]
+    fn parse_package_url_splits_package_and_path() {
+        assert_eq!(
+            parse_package_url("package://rm_75_6fb_description/urdf/RM75-6F.urdf"),
+            Some(("rm_75_6fb_description", "urdf/RM75-6F.urdf"))
+        );
+        assert_eq!(parse_package_url(FIXTURE), None);
+    }
+
+    #[test]
+    fn package_source_resolves_against_package_paths() {
+        let mut package_paths = HashMap::new();
+        package_paths.insert(
+            "rm_75_6fb_description".to_string(),
+            PathBuf::from("./urdf/rm_75_6fb_description"),
+        );
+        let contents = PackageSource {
+            package: "rm_75_6fb_description".to_string(),
+            relative_path: "urdf/RM75-6F.urdf".to_string(),
+            package_paths: &package_paths,
+        }
+        .load()
+        .unwrap();
+        assert!(contents.contains("<robot"));
+    }
+
+    #[test]
+    fn package_source_errors_on_unknown_package() {
+        let package_paths = HashMap::new();
+        let err = PackageSource {
+            package: "nonexistent".to_string(),
+            relative_path: "urdf/RM75-6F.urdf".to_string(),
+            package_paths: &package_paths,
+        }
+        .load()
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn is_weburl_test() {
+        assert!(is_weburl("http://example.com/robot.urdf"));
+        assert!(is_weburl("https://example.com/robot.urdf"));
+        assert!(!is_weburl(FIXTURE));
+        assert!(!is_weburl("package://pkg/robot.urdf"));
+    }
+}
@@ -1,9 +1,17 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+
 use super::bfs::bfs;
+use super::source::{file_source, is_weburl, parse_package_url, PackageSource, UrdfSource};
 use super::utils::*;
 use super::Link;
 use super::Model;
 use urdf_rs::read_from_string;
 
+#[cfg(feature = "web")]
+use super::source::HttpSource;
+
 impl Model {
     pub fn from_urdf_string(str: &str) -> std::io::Result<Self> {
         // construct a Model from robot
@@ -25,13 +33,19 @@ impl Model {
         // link_id -> link_id
         let link_graph = construct_link_graph(&temp_map);
 
+        // joint_name -> link_id, used to resolve <mimic> tags to a link
+        let joint_name_to_link = temp_map
+            .values()
+            .filter_map(|(id, joint, _)| joint.as_ref().map(|j| (j.name.clone(), *id)))
+            .collect();
+
         // construct a map: link_id -> link(empty)
         let mut link_map = construct_link_map(temp_map);
 
         let bfs = bfs(&link_graph, start);
 
         // fullfill the link in link_map(space_spatial_twist & global_zero_pose)
-        fullfill_link_map(&mut link_map, &link_graph, &bfs);
+        fullfill_link_map(&mut link_map, &link_graph, &bfs, &joint_name_to_link)?;
 
         let mut v = link_map.into_iter().collect::<Vec<(usize, Link)>>();
         v.sort_by(|(a, _), (b, _)| a.cmp(b));
@@ -43,21 +57,52 @@ impl Model {
         })
     }
 
-    pub fn from_urdf(url: &str) -> std::io::Result<Self> {
-        // check url is a file or a web url
-        // if url is a file, read the file
-        // if url is a web url, read the url
-        let str = if url_is_urdf_file(url) {
-            read_file(url)?
-        } else if url_is_weburl(url) {
-            read_web(url)?
+    /// Loads a URDF from `url`, dispatching on its shape rather than its
+    /// file extension: `package://<package>/<path>` is resolved against
+    /// `package_paths`, `http(s)://` is fetched over the network (requires
+    /// the `web` feature), and anything else is read as a filesystem path.
+    pub fn from_urdf_with_packages(
+        url: &str,
+        package_paths: &HashMap<String, PathBuf>,
+    ) -> std::io::Result<Self> {
+        let contents = if let Some((package, relative_path)) = parse_package_url(url) {
+            PackageSource {
+                package: package.to_string(),
+                relative_path: relative_path.to_string(),
+                package_paths,
+            }
+            .load()?
+        } else if is_weburl(url) {
+            #[cfg(feature = "web")]
+            {
+                HttpSource {
+                    url: url.to_string(),
+                }
+                .load()?
+            }
+            #[cfg(not(feature = "web"))]
+            {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    "fetching URDFs over http(s) requires the `web` feature",
+                ));
+            }
         } else {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "url is not a file or a web url",
-            ));
+            file_source(url).load()?
         };
-        Self::from_urdf_string(&str)
+        Self::from_urdf_string(&contents)
+    }
+
+    pub fn from_urdf(url: &str) -> std::io::Result<Self> {
+        Self::from_urdf_with_packages(url, &HashMap::new())
+    }
+
+    /// Builds a `Model` from any `Read`er, e.g. an in-memory or
+    /// xacro-expanded URDF string that never touches disk.
+    pub fn from_reader(mut reader: impl Read) -> std::io::Result<Self> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        Self::from_urdf_string(&contents)
     }
 }
 
@@ -71,4 +116,12 @@ mod tests {
         let model = Model::from_urdf(file_path).unwrap();
         assert_eq!(model.links.len(), 8);
     }
+
+    #[test]
+    fn from_reader_test() {
+        let file_path = "./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf";
+        let file = std::fs::File::open(file_path).unwrap();
+        let model = Model::from_reader(file).unwrap();
+        assert_eq!(model.links.len(), 8);
+    }
 }
@@ -1,41 +1,9 @@
-use std::{collections::HashMap, io::Read};
+use std::collections::HashMap;
 
 use liealg::{se3, Adjoint, Group, SE3, SO3};
 use nalgebra::{Matrix3, Matrix6};
 
-use super::{spatial_inertial::to_local_spatial_inertial, Joint, Link};
-
-pub(super) fn url_is_urdf_file(url: &str) -> bool {
-    std::path::Path::new(url).exists() && (url.ends_with(".urdf") || url.ends_with(".URDF"))
-}
-
-pub(super) fn url_is_weburl(url: &str) -> bool {
-    // check if url is a web url
-    // if url starts with http:// or https://
-    // and ends with .urdf
-    (url.starts_with("http://") || url.starts_with("https://"))
-        && (url.ends_with(".urdf") || url.ends_with(".URDF"))
-}
-
-pub(super) fn read_file(url: &str) -> std::io::Result<String> {
-    // read file from url
-    // if url is a file, read the file
-    let path = std::path::Path::new(url);
-    let mut file = std::fs::File::open(path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    Ok(contents)
-}
-
-pub(super) fn read_web(url: &str) -> std::io::Result<String> {
-    // read web from url
-    // if url is a web url, read the url
-    let mut response = reqwest::blocking::get(url)
-        .map_err(std::io::Error::other)?;
-    let mut contents = String::new();
-    response.read_to_string(&mut contents)?;
-    Ok(contents)
-}
+use super::{spatial_inertial::to_local_spatial_inertial, Joint, JointLimits, Link, Mimic};
 
 pub(super) fn temp_link_map(
     robot: urdf_rs::Robot,
@@ -99,7 +67,11 @@ pub(super) fn construct_link_map(
                     local_spatial_inertial: spatial_inertia(&l),
 
                     // urdf_link: l,
-                    joint: Joint { urdf_joint: j },
+                    joint: Joint {
+                        urdf_joint: j,
+                        mimic: None,
+                        limits: None,
+                    },
                 },
             )
         })
@@ -124,7 +96,8 @@ pub(super) fn fullfill_link_map(
     link_map: &mut HashMap<usize, Link>,
     graph: &petgraph::graphmap::DiGraphMap<usize, ()>,
     bfs: &[usize],
-) {
+    joint_name_to_link: &HashMap<String, usize>,
+) -> std::io::Result<()> {
     for link in bfs {
         // get the parent link
         let parent_index = graph
@@ -136,25 +109,66 @@ pub(super) fn fullfill_link_map(
                 .unwrap()
                 .global_zero_pose
                 .clone();
-            let relative_pose = pose_to_se3(
-                &link_map
-                    .get(link)
-                    .unwrap()
-                    .joint
-                    .urdf_joint
-                    .as_ref()
-                    .unwrap()
-                    .origin,
-            );
-            let twist = joint_twist(
-                link_map
-                    .get(link)
-                    .unwrap()
-                    .joint
-                    .urdf_joint
-                    .as_ref()
-                    .unwrap(),
-            );
+            let urdf_joint = link_map
+                .get(link)
+                .unwrap()
+                .joint
+                .urdf_joint
+                .as_ref()
+                .unwrap()
+                .clone();
+
+            let relative_pose = pose_to_se3(&urdf_joint.origin);
+
+            let mut twists = joint_twist(&urdf_joint);
+            if twists.len() > 1 {
+                // `Planar`/`Floating` joints are unconstrained in more than
+                // one axis; forward/inverse kinematics and dynamics only
+                // drive a single axis per joint, so rather than silently
+                // modeling a multi-DOF joint as whichever axis happened to
+                // come first, refuse to parse it
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    format!(
+                        "joint `{}` is a {:?} joint with {} degrees of freedom; only fixed and single-DOF joint types are supported",
+                        urdf_joint.name,
+                        urdf_joint.joint_type,
+                        twists.len(),
+                    ),
+                ));
+            }
+            let twist = if twists.is_empty() {
+                se3::identity()
+            } else {
+                twists.remove(0)
+            };
+
+            // an unresolvable `<mimic joint="...">` (typo, forward reference
+            // to a joint that was dropped, etc.) drops the mimic
+            // relationship entirely rather than silently mimicking itself.
+            // Likewise a mimic whose source is itself a mimic joint (a
+            // chain, which URDF doesn't forbid) is dropped instead of
+            // silently resolving against the source's unused `theta` slot,
+            // since `Model::resolved_position`/`resolved_rate` don't
+            // recurse through chains.
+            let mimic = urdf_joint.mimic.as_ref().and_then(|mimic| {
+                joint_name_to_link.get(&mimic.joint).and_then(|&source| {
+                    let source_is_mimic = link_map
+                        .get(&source)
+                        .and_then(|l| l.joint.urdf_joint.as_ref())
+                        .is_some_and(|j| j.mimic.is_some());
+                    if source_is_mimic {
+                        None
+                    } else {
+                        Some(Mimic {
+                            source,
+                            multiplier: mimic.multiplier.unwrap_or(1.),
+                            offset: mimic.offset.unwrap_or(0.),
+                        })
+                    }
+                })
+            });
+
             let link_mut = link_map.get_mut(link).unwrap();
 
             let global_pose = parent_global_pose * relative_pose.clone();
@@ -165,35 +179,93 @@ pub(super) fn fullfill_link_map(
 
             link_mut.local_spatial_screw = twist;
             link_mut.space_spatial_screw = global_screw;
+            link_mut.joint.mimic = mimic;
+            link_mut.joint.limits = joint_limits(&urdf_joint);
         }
     }
+    Ok(())
+}
+
+// URDF <limit> data; continuous joints are exempt since their position is
+// unbounded, so they carry no `JointLimits` at all
+fn joint_limits(joint: &urdf_rs::Joint) -> Option<JointLimits> {
+    match joint.joint_type {
+        urdf_rs::JointType::Revolute | urdf_rs::JointType::Prismatic => Some(JointLimits {
+            lower: joint.limit.lower,
+            upper: joint.limit.upper,
+            velocity: joint.limit.velocity,
+            effort: joint.limit.effort,
+        }),
+        _ => None,
+    }
+}
+
+// axis as a unit vector
+fn normalize(axis: [f64; 3]) -> [f64; 3] {
+    let norm = (axis[0].powi(2) + axis[1].powi(2) + axis[2].powi(2)).sqrt();
+    [axis[0] / norm, axis[1] / norm, axis[2] / norm]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
 }
 
-pub(super) fn joint_twist(joint: &urdf_rs::Joint) -> liealg::se3<f64> {
+// two unit vectors spanning the plane orthogonal to `axis`
+fn plane_basis(axis: [f64; 3]) -> ([f64; 3], [f64; 3]) {
+    let seed = if axis[0].abs() < 0.9 {
+        [1., 0., 0.]
+    } else {
+        [0., 1., 0.]
+    };
+    let e1 = normalize(cross(axis, seed));
+    let e2 = normalize(cross(axis, e1));
+    (e1, e2)
+}
+
+// the joint's screw axes. Single-DOF and fixed joints return at most one;
+// `Planar`/`Floating` return more than one, which `fullfill_link_map`
+// rejects, since forward/inverse kinematics and dynamics only ever drive a
+// single axis per joint.
+pub(super) fn joint_twist(joint: &urdf_rs::Joint) -> Vec<liealg::se3<f64>> {
     match joint.joint_type {
         urdf_rs::JointType::Revolute | urdf_rs::JointType::Continuous => {
-            let norm =
-                (joint.axis.xyz[0].powi(2) + joint.axis.xyz[1].powi(2) + joint.axis.xyz[2].powi(2))
-                    .sqrt();
-            let norm_x = joint.axis.xyz[0] / norm;
-            let norm_y = joint.axis.xyz[1] / norm;
-            let norm_z = joint.axis.xyz[2] / norm;
-            liealg::se3::<f64>::new([norm_x, norm_y, norm_z], [0., 0., 0.])
+            let axis = normalize(joint.axis.xyz.0);
+            vec![liealg::se3::<f64>::new(axis, [0., 0., 0.])]
         }
 
         urdf_rs::JointType::Prismatic => {
-            let norm =
-                (joint.axis.xyz[0].powi(2) + joint.axis.xyz[1].powi(2) + joint.axis.xyz[2].powi(2))
-                    .sqrt();
-            let norm_x = joint.axis.xyz[0] / norm;
-            let norm_y = joint.axis.xyz[1] / norm;
-            let norm_z = joint.axis.xyz[2] / norm;
-            liealg::se3::<f64>::new([0., 0., 0.], [norm_x, norm_y, norm_z])
+            let axis = normalize(joint.axis.xyz.0);
+            vec![liealg::se3::<f64>::new([0., 0., 0.], axis)]
         }
 
-        urdf_rs::JointType::Fixed => liealg::se3::<f64>::identity(),
+        urdf_rs::JointType::Fixed => vec![],
+
+        urdf_rs::JointType::Planar => {
+            // two independent in-plane translations, orthogonal to the
+            // joint axis (the plane's normal)
+            let (e1, e2) = plane_basis(normalize(joint.axis.xyz.0));
+            vec![
+                liealg::se3::<f64>::new([0., 0., 0.], e1),
+                liealg::se3::<f64>::new([0., 0., 0.], e2),
+            ]
+        }
 
-        _ => panic!("joint type not supported"),
+        urdf_rs::JointType::Floating => {
+            // unconstrained 6-DOF: three independent rotations about the
+            // world axes, then three independent translations
+            vec![
+                liealg::se3::<f64>::new([1., 0., 0.], [0., 0., 0.]),
+                liealg::se3::<f64>::new([0., 1., 0.], [0., 0., 0.]),
+                liealg::se3::<f64>::new([0., 0., 1.], [0., 0., 0.]),
+                liealg::se3::<f64>::new([0., 0., 0.], [1., 0., 0.]),
+                liealg::se3::<f64>::new([0., 0., 0.], [0., 1., 0.]),
+                liealg::se3::<f64>::new([0., 0., 0.], [0., 0., 1.]),
+            ]
+        }
     }
 }
 
@@ -205,40 +277,6 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    fn url_is_file_test() {
-        assert!(url_is_urdf_file(
-            "./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf"
-        ));
-        assert!(!url_is_urdf_file("src/model/urdf/test.txt"));
-    }
-
-    #[test]
-    fn url_is_weburl_test() {
-        assert!(url_is_weburl("http://www.example.com/urdf/test.urdf"));
-        assert!(url_is_weburl("https://www.example.com/urdf/test.urdf"));
-        assert!(!url_is_weburl("http://www.example.com/urdf/test.txt"));
-        assert!(!url_is_weburl("https://www.example.com/urdf/test.txt"));
-        assert!(!url_is_weburl("http://www.example.com/urdf/test"));
-        assert!(!url_is_weburl("https://www.example.com/urdf/test"));
-    }
-
-    #[test]
-    fn read_file_test() {
-        let path = std::path::Path::new("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf");
-        let contents = read_file(path.to_str().unwrap()).unwrap();
-        assert!(!contents.is_empty());
-    }
-
-    #[test]
-    fn read_web_test() {
-        let url = "https://gitee.com/RealManRobot/rm_models/raw/main/RM75/urdf/rm_75_6fb_description/urdf/RM75-6F.urdf";
-        let web_contents = read_web(url).unwrap();
-        let path = std::path::Path::new("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf");
-        let file_contents = read_file(path.to_str().unwrap()).unwrap();
-        assert!(web_contents == file_contents);
-    }
-
     #[test]
     fn temp_link_map_test() {
         let robot = urdf_rs::read_file("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf").unwrap();
@@ -308,7 +346,10 @@ mod tests {
 
         link_map.iter().for_each(|(i, l)| {
             if let Some(joint) = &l.joint.urdf_joint {
-                println!("{}: {:.3}", i, joint_twist(joint).vee());
+                let twists = joint_twist(joint);
+                for twist in &twists {
+                    println!("{}: {:.3}", i, twist.vee());
+                }
             }
         });
     }
@@ -318,8 +359,12 @@ mod tests {
         let robot = urdf_rs::read_file("./urdf/rm_75_6fb_description/urdf/RM75-6F.urdf").unwrap();
         let map = temp_link_map(robot);
         let graph = construct_link_graph(&map);
+        let joint_name_to_link = map
+            .values()
+            .filter_map(|(id, joint, _)| joint.as_ref().map(|j| (j.name.clone(), *id)))
+            .collect();
         let mut link_map = construct_link_map(map);
-        fullfill_link_map(&mut link_map, &graph, &bfs(&graph, 0));
+        fullfill_link_map(&mut link_map, &graph, &bfs(&graph, 0), &joint_name_to_link).unwrap();
         // looks works fine
         link_map.iter().for_each(|(i, l)| {
             println!(
@@ -331,4 +376,96 @@ mod tests {
             );
         });
     }
+
+    const CHAINED_MIMIC_URDF: &str = r#"<?xml version="1.0"?>
+<robot name="chained_mimic">
+  <link name="base">
+    <inertial>
+      <origin xyz="0 0 0" rpy="0 0 0"/>
+      <mass value="1"/>
+      <inertia ixx="1" ixy="0" ixz="0" iyy="1" iyz="0" izz="1"/>
+    </inertial>
+  </link>
+  <link name="driver">
+    <inertial>
+      <origin xyz="0 0 0" rpy="0 0 0"/>
+      <mass value="1"/>
+      <inertia ixx="1" ixy="0" ixz="0" iyy="1" iyz="0" izz="1"/>
+    </inertial>
+  </link>
+  <link name="mimic_a">
+    <inertial>
+      <origin xyz="0 0 0" rpy="0 0 0"/>
+      <mass value="1"/>
+      <inertia ixx="1" ixy="0" ixz="0" iyy="1" iyz="0" izz="1"/>
+    </inertial>
+  </link>
+  <link name="mimic_b">
+    <inertial>
+      <origin xyz="0 0 0" rpy="0 0 0"/>
+      <mass value="1"/>
+      <inertia ixx="1" ixy="0" ixz="0" iyy="1" iyz="0" izz="1"/>
+    </inertial>
+  </link>
+  <joint name="driver_joint" type="revolute">
+    <parent link="base"/>
+    <child link="driver"/>
+    <origin xyz="0 0 0" rpy="0 0 0"/>
+    <axis xyz="0 0 1"/>
+    <limit lower="-1" upper="1" effort="1" velocity="1"/>
+  </joint>
+  <joint name="mimic_a_joint" type="revolute">
+    <parent link="driver"/>
+    <child link="mimic_a"/>
+    <origin xyz="0 0 0" rpy="0 0 0"/>
+    <axis xyz="0 0 1"/>
+    <limit lower="-1" upper="1" effort="1" velocity="1"/>
+    <mimic joint="driver_joint" multiplier="1" offset="0"/>
+  </joint>
+  <joint name="mimic_b_joint" type="revolute">
+    <parent link="mimic_a"/>
+    <child link="mimic_b"/>
+    <origin xyz="0 0 0" rpy="0 0 0"/>
+    <axis xyz="0 0 1"/>
+    <limit lower="-1" upper="1" effort="1" velocity="1"/>
+    <mimic joint="mimic_a_joint" multiplier="1" offset="0"/>
+  </joint>
+</robot>
+"#;
+
+    // a mimic joint whose source is itself a mimic joint (a chain) is
+    // dropped rather than silently resolved against the source's unused
+    // `theta` slot, since `resolved_position`/`resolved_rate` don't recurse
+    #[test]
+    fn fullfill_link_map_drops_chained_mimic() {
+        let robot = urdf_rs::read_from_string(CHAINED_MIMIC_URDF).unwrap();
+        let map = temp_link_map(robot);
+        let graph = construct_link_graph(&map);
+        let start = map
+            .iter()
+            .find(|(k, _)| k.is_none())
+            .map(|(_, v)| v.0)
+            .unwrap();
+        let joint_name_to_link = map
+            .values()
+            .filter_map(|(id, joint, _)| joint.as_ref().map(|j| (j.name.clone(), *id)))
+            .collect();
+        let mut link_map = construct_link_map(map);
+        fullfill_link_map(&mut link_map, &graph, &bfs(&graph, start), &joint_name_to_link).unwrap();
+
+        let joint_named = |name: &str| {
+            link_map
+                .values()
+                .find(|l| {
+                    l.joint
+                        .urdf_joint
+                        .as_ref()
+                        .is_some_and(|j| j.name == name)
+                })
+                .unwrap()
+        };
+
+        assert!(joint_named("mimic_a_joint").joint.mimic.is_some());
+        assert!(joint_named("mimic_b_joint").joint.mimic.is_none());
+    }
 }